@@ -0,0 +1,213 @@
+//! Offline rendering and exporting of DSP graphs to audio files.
+//!
+//! [`DspSource::generate_raw_bytes`] used to hardcode 16-bit WAV into an in-memory buffer. This
+//! module adds a [`SampleFormat`] choice (16-bit PCM or 32-bit float) and a way to render
+//! straight to disk via [`DspSource::export_to_file`], so a graph can be baked into a reusable
+//! asset instead of only ever feeding `kira`.
+
+use std::{
+    fs::File,
+    io,
+    path::Path,
+};
+
+use fundsp::hacker32::Wave32;
+
+use crate::DspSource;
+
+/// Sample format used when encoding a rendered [`Wave32`] to bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// 16-bit signed integer PCM, `fundsp`'s `write_wav16`.
+    Int16,
+    /// 32-bit IEEE float, `fundsp`'s `write_wav32`.
+    Float32,
+}
+
+/// File format written by [`DspSource::export_to_file`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFileFormat {
+    /// WAV container, encoded according to the requested [`SampleFormat`].
+    Wav,
+    /// OGG Vorbis container.
+    ///
+    /// Requires the `ogg` feature.
+    #[cfg(feature = "ogg")]
+    Ogg,
+    /// FLAC container.
+    ///
+    /// Requires the `flac` feature.
+    #[cfg(feature = "flac")]
+    Flac,
+}
+
+impl AudioFileFormat {
+    /// Infers the file format from a path's extension (`wav`/`wave`, `ogg`, `flac`), if
+    /// recognized.
+    #[must_use]
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "wav" | "wave" => Some(Self::Wav),
+            #[cfg(feature = "ogg")]
+            "ogg" => Some(Self::Ogg),
+            #[cfg(feature = "flac")]
+            "flac" => Some(Self::Flac),
+            _ => None,
+        }
+    }
+}
+
+impl DspSource {
+    /// Renders this source to a [`Wave32`] without encoding it to any particular file format.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the graph's `length` is not finite. Infinite graphs cannot be rendered
+    /// offline; use [`into_stream`](Self::into_stream) instead.
+    #[must_use]
+    pub fn render_wave(mut self, sample_rate: f64) -> Wave32 {
+        assert!(
+            self.length.is_finite(),
+            "cannot render a DSP source of infinite length to a fixed buffer; use `into_stream` instead"
+        );
+
+        Wave32::render(sample_rate, self.length, self.graph.as_mut())
+    }
+
+    /// Generate the raw bytes of a DSP graph given the sample rate and the [`SampleFormat`] to
+    /// encode the WAV data with.
+    ///
+    /// # Panics
+    ///
+    /// This panics when it cannot write the DSP graph to a wave buffer.
+    #[must_use]
+    pub fn generate_raw_bytes(
+        self,
+        sample_rate: f64,
+        sample_format: SampleFormat,
+    ) -> io::Cursor<Vec<u8>> {
+        let wave = self.render_wave(sample_rate);
+        let mut buffer = Vec::new();
+
+        write_wav(&wave, &mut buffer, sample_format)
+            .unwrap_or_else(|err| panic!("Cannot write wave to buffer. Error: {err:?}"));
+
+        io::Cursor::new(buffer)
+    }
+
+    /// Renders this source and writes it to `path` in the given [`AudioFileFormat`] and
+    /// [`SampleFormat`].
+    ///
+    /// The sample format applies to WAV as described on [`SampleFormat`]. OGG output ignores it.
+    /// FLAC output writes [`SampleFormat::Int16`] as 16-bit and [`SampleFormat::Float32`] as
+    /// 24-bit integer PCM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` cannot be created or written to.
+    pub fn export_to_file(
+        self,
+        path: impl AsRef<Path>,
+        sample_rate: f64,
+        format: AudioFileFormat,
+        sample_format: SampleFormat,
+    ) -> io::Result<()> {
+        let wave = self.render_wave(sample_rate);
+        let mut file = File::create(path)?;
+
+        match format {
+            AudioFileFormat::Wav => write_wav(&wave, &mut file, sample_format),
+            #[cfg(feature = "ogg")]
+            AudioFileFormat::Ogg => ogg::write(&wave, &mut file),
+            #[cfg(feature = "flac")]
+            AudioFileFormat::Flac => flac::write(&wave, &mut file, sample_format),
+        }
+    }
+}
+
+fn write_wav(wave: &Wave32, out: &mut impl io::Write, sample_format: SampleFormat) -> io::Result<()> {
+    match sample_format {
+        SampleFormat::Int16 => wave.write_wav16(out),
+        SampleFormat::Float32 => wave.write_wav32(out),
+    }
+    .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))
+}
+
+#[cfg(feature = "ogg")]
+mod ogg {
+    use std::io;
+
+    use fundsp::hacker32::Wave32;
+    use vorbis_rs::VorbisEncoderBuilder;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(super) fn write(wave: &Wave32, out: &mut impl io::Write) -> io::Result<()> {
+        let to_io_err = |err: vorbis_rs::VorbisError| io::Error::new(io::ErrorKind::Other, err);
+
+        let sample_rate = std::num::NonZeroU32::new(wave.sample_rate() as u32)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "sample rate must be non-zero"))?;
+        let channels = std::num::NonZeroU8::new(wave.channels() as u8)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "graph must have at least one output channel"))?;
+
+        let mut encoder = VorbisEncoderBuilder::new(sample_rate, channels, out)
+            .map_err(to_io_err)?
+            .build()
+            .map_err(to_io_err)?;
+
+        let channel_buffers: Vec<Vec<f32>> = (0..wave.channels())
+            .map(|channel| (0..wave.len()).map(|frame| wave.at(channel, frame)).collect())
+            .collect();
+        let channel_refs: Vec<&[f32]> = channel_buffers.iter().map(Vec::as_slice).collect();
+
+        encoder.encode_audio_block(&channel_refs).map_err(to_io_err)?;
+        encoder.finish().map_err(to_io_err)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "flac")]
+mod flac {
+    use std::io;
+
+    use flacenc::{component::BitRepr, error::Verified};
+    use fundsp::hacker32::Wave32;
+
+    use super::SampleFormat;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(super) fn write(
+        wave: &Wave32,
+        out: &mut impl io::Write,
+        sample_format: SampleFormat,
+    ) -> io::Result<()> {
+        let (bits_per_sample, full_scale) = match sample_format {
+            SampleFormat::Int16 => (16, f32::from(i16::MAX)),
+            SampleFormat::Float32 => (24, (1_i32 << 23) as f32 - 1.0),
+        };
+
+        let samples: Vec<i32> = (0..wave.len())
+            .flat_map(|frame| {
+                (0..wave.channels()).map(move |channel| {
+                    (wave.at(channel, frame).clamp(-1.0, 1.0) * full_scale) as i32
+                })
+            })
+            .collect();
+
+        let config = flacenc::config::Encoder::default();
+        let source = flacenc::source::MemSource::from_samples(
+            &samples,
+            wave.channels(),
+            bits_per_sample,
+            wave.sample_rate() as usize,
+        );
+        let flac_stream: Verified<flacenc::component::Stream> =
+            flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+
+        let mut sink = flacenc::bitsink::ByteSink::new();
+        flac_stream.write(&mut sink).map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+        out.write_all(sink.as_slice())
+    }
+}
@@ -28,21 +28,39 @@
 
 use std::{
     any::{type_name, Any, TypeId},
-    io::Cursor,
+    sync::Arc,
 };
 
 use bevy::{
     prelude::{App, Plugin, Res, Commands, ResMut, StartupStage, SystemStage, StageLabel},
     utils::HashMap, asset::{Assets, Handle},
 };
+#[cfg(feature = "kira")]
 use bevy_kira_audio::AudioSource;
 pub use fundsp::hacker32;
-use fundsp::hacker32::{AudioUnit32, Wave32};
+use fundsp::hacker32::{AudioUnit32, Shared};
+#[cfg(feature = "kira")]
 use kira::sound::{
     static_sound::{StaticSoundData, StaticSoundSettings},
     FromFileError,
 };
 
+#[cfg(feature = "kira")]
+mod streaming;
+#[cfg(feature = "kira")]
+pub use streaming::StreamingDspSource;
+
+mod params;
+pub use params::{DspParameters, ParamHandle};
+
+mod export;
+pub use export::{AudioFileFormat, SampleFormat};
+
+#[cfg(feature = "bevy_audio")]
+mod native;
+#[cfg(feature = "bevy_audio")]
+pub use native::{DspAudioDecoder, DspAudioSource};
+
 /// A source of a DSP graph.
 pub struct DspSource {
     graph: Box<dyn AudioUnit32>,
@@ -63,27 +81,16 @@ impl DspSource {
     pub fn from_boxed(graph: Box<dyn AudioUnit32>, length: f64) -> Self {
         Self { graph, length }
     }
+}
 
-    /// Generate the raw bytes of a DSP graph given the sample rate and its length.
-    ///
-    /// # Panics
-    ///
-    /// This panics when it cannot write the DSP graph to a wave buffer.
-    #[must_use]
-    pub fn generate_raw_bytes(mut self, sample_rate: f64) -> Cursor<Vec<u8>> {
-        let wave = Wave32::render(sample_rate, self.length, self.graph.as_mut());
-
-        let mut buffer = Vec::new();
-
-        wave.write_wav16(&mut buffer)
-            .unwrap_or_else(|err| panic!("Cannot write wave to buffer. Error: {err:?}"));
-
-        Cursor::new(buffer)
-    }
-
-    /// Returns a [`StaticSoundData`].
+/// `kira`-specific conversion, only available when the `kira` feature is enabled.
+#[cfg(feature = "kira")]
+impl DspSource {
+    /// Returns a [`StaticSoundData`], encoded with the given [`SampleFormat`].
     ///
-    /// This is useful if you are using [`bevy_kira_audio`].
+    /// This is useful if you are using [`bevy_kira_audio`]. Use [`SampleFormat::Float32`] to
+    /// build the sound data at full float precision instead of 16-bit PCM, which is what
+    /// [`DspManager::add_assets`] uses.
     ///
     /// [`StaticSoundData`]: kira::sound::static_sound::StaticSoundData
     ///
@@ -94,8 +101,9 @@ impl DspSource {
         self,
         sample_rate: f64,
         settings: StaticSoundSettings,
+        sample_format: SampleFormat,
     ) -> Result<StaticSoundData, FromFileError> {
-        let raw_bytes = self.generate_raw_bytes(sample_rate);
+        let raw_bytes = self.generate_raw_bytes(sample_rate, sample_format);
 
         StaticSoundData::from_cursor(raw_bytes, settings)
     }
@@ -119,26 +127,32 @@ where
 
 /// A DSP graph struct used in the manager.
 pub struct DspGraph {
-    func: Box<dyn FnDspGraph>,
+    func: Arc<dyn FnDspGraph>,
     length: f64,
+    #[cfg(feature = "kira")]
     settings: StaticSoundSettings,
+    /// Named parameters registered through [`DspManager::add_graph_with_params`], if any.
+    params: HashMap<String, ParamHandle>,
 }
 
 impl DspGraph {
     /// Create a new graph from the graph function and its length in seconds.
     #[must_use]
-    pub fn new(func: Box<dyn FnDspGraph>, length: f64) -> Self {
+    pub fn new(func: Arc<dyn FnDspGraph>, length: f64) -> Self {
         Self {
             func,
             length,
+            #[cfg(feature = "kira")]
             settings: StaticSoundSettings::default(),
+            params: HashMap::default(),
         }
     }
 
     /// Create a new graph from the graph function, its length in seconds, and `kira`'s [`StaticSoundSettings`].
+    #[cfg(feature = "kira")]
     #[must_use]
     pub fn with_settings(
-        func: Box<dyn FnDspGraph>,
+        func: Arc<dyn FnDspGraph>,
         length: f64,
         settings: StaticSoundSettings,
     ) -> Self {
@@ -146,6 +160,24 @@ impl DspGraph {
             func,
             length,
             settings,
+            params: HashMap::default(),
+        }
+    }
+
+    /// Create a new graph from the graph function, its length in seconds, and its named
+    /// [`ParamHandle`]s.
+    #[must_use]
+    pub fn with_params(
+        func: Arc<dyn FnDspGraph>,
+        length: f64,
+        params: HashMap<String, ParamHandle>,
+    ) -> Self {
+        Self {
+            func,
+            length,
+            #[cfg(feature = "kira")]
+            settings: StaticSoundSettings::default(),
+            params,
         }
     }
 }
@@ -154,7 +186,7 @@ impl DspGraph {
 /// This is automatically added as a resource.
 pub struct DspManager {
     graphs: HashMap<TypeId, DspGraph>,
-    #[allow(dead_code)] // This is only used when `kira` is enabled.
+    #[allow(dead_code)] // Only used when the `kira` or `bevy_audio` feature is enabled.
     sample_rate: f64,
 }
 
@@ -187,11 +219,12 @@ impl DspManager {
     /// ```
     pub fn add_graph<F: FnDspGraph>(&mut self, f: F, length: f64) -> &mut Self {
         self.graphs
-            .insert(TypeId::of::<F>(), DspGraph::new(Box::new(f), length));
+            .insert(TypeId::of::<F>(), DspGraph::new(Arc::new(f), length));
         self
     }
 
     /// Add a new graph into the manager with the given settings.
+    #[cfg(feature = "kira")]
     pub fn add_graph_with_settings<F: FnDspGraph>(
         &mut self,
         f: F,
@@ -200,11 +233,87 @@ impl DspManager {
     ) -> &mut Self {
         self.graphs.insert(
             TypeId::of::<F>(),
-            DspGraph::with_settings(Box::new(f), length, settings),
+            DspGraph::with_settings(Arc::new(f), length, settings),
         );
         self
     }
 
+    /// Generate asset handles for all DSP graphs, playable through Bevy's native `bevy_audio`
+    /// backend instead of `kira`.
+    ///
+    /// This is only available when the `bevy_audio` feature is enabled, and gives the same
+    /// `add_graph` ergonomics as [`add_assets`](Self::add_assets) to users who are not using
+    /// `bevy_kira_audio`.
+    #[cfg(feature = "bevy_audio")]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn add_native_assets(&self, assets: &mut Assets<DspAudioSource>) -> DspNativeAssets {
+        let handles = self
+            .graphs
+            .iter()
+            .map(|(type_id, graph)| {
+                let source = DspAudioSource::new(
+                    Arc::clone(&graph.func),
+                    graph.length,
+                    self.sample_rate as u32,
+                );
+                (*type_id, assets.add(source))
+            })
+            .collect();
+        DspNativeAssets::new(handles)
+    }
+
+    /// Add a new graph into the manager together with its named, runtime-tweakable parameters.
+    ///
+    /// Each parameter is a [`Shared`] atomic that `f`'s graph already reads through a
+    /// `var(&shared)` node. The returned [`ParamHandle`]s wrap those same atomics: call
+    /// [`ParamHandle::set`] to jump to a value immediately, or [`ParamHandle::tween`] to smoothly
+    /// transition to it over time (advanced once per frame by [`DspPlugin`]). Keep the returned
+    /// handles around, e.g. in your own resource, to control the graph after this call returns.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bevy_fundsp::prelude::*;
+    ///
+    /// fn cutoff_sweep(cutoff: &Shared) -> impl AudioUnit32 {
+    ///     (noise() | var(cutoff) | constant(1.0)) >> lowpass()
+    /// }
+    ///
+    /// fn init_graph(mut dsp_manager: NonSendMut<DspManager>) {
+    ///     let cutoff = shared(440.0);
+    ///     let graph_cutoff = cutoff.clone();
+    ///     let handles = dsp_manager.add_graph_with_params(
+    ///         move || cutoff_sweep(&graph_cutoff),
+    ///         f64::INFINITY,
+    ///         vec![("cutoff", cutoff.clone())],
+    ///     );
+    ///     handles["cutoff"].tween(2000.0, 3.0, true);
+    /// }
+    /// ```
+    pub fn add_graph_with_params<F: FnDspGraph>(
+        &mut self,
+        f: F,
+        length: f64,
+        params: Vec<(&'static str, Shared)>,
+    ) -> HashMap<&'static str, ParamHandle> {
+        let handles: HashMap<&'static str, ParamHandle> = params
+            .into_iter()
+            .map(|(name, shared)| (name, ParamHandle::new(shared)))
+            .collect();
+
+        let graph_params = handles
+            .iter()
+            .map(|(name, handle)| ((*name).to_string(), handle.clone()))
+            .collect();
+
+        self.graphs.insert(
+            TypeId::of::<F>(),
+            DspGraph::with_params(Arc::new(f), length, graph_params),
+        );
+
+        handles
+    }
+
     /// Remove a graph from the manager.
     pub fn remove_graph<F: FnDspGraph>(&mut self, f: &F) -> &mut Self {
         self.graphs.remove(&Any::type_id(f));
@@ -220,9 +329,14 @@ impl DspManager {
 
     /// Generate asset handles for all DSP graphs.
     ///
+    /// Graphs with `length == f64::INFINITY` are skipped: they cannot be pre-rendered into a
+    /// fixed-length `kira` sound, and must instead be played through
+    /// [`DspSource::into_stream`](crate::DspSource::into_stream).
+    ///
     /// # Panics
     ///
-    /// This panics if the [`DspSource`] cannot be converted to a `kira` sound data.
+    /// This panics if a finite-length [`DspSource`] cannot be converted to a `kira` sound data.
+    #[cfg(feature = "kira")]
     pub fn add_assets(
         &self,
         assets: &mut Assets<AudioSource>,
@@ -230,11 +344,12 @@ impl DspManager {
         let handles = self
             .graphs
             .iter()
+            .filter(|(_, graph)| graph.length.is_finite())
             .map(|(type_id, graph)| {
                 let audio_graph = graph.func.generate_graph();
                 let dsp_source = DspSource::from_boxed(audio_graph, graph.length);
                 let sound = dsp_source
-                    .into_kira_sound_data(self.sample_rate, graph.settings)
+                    .into_kira_sound_data(self.sample_rate, graph.settings, SampleFormat::Int16)
                     .unwrap_or_else(|err| {
                         panic!("Cannot convert DSP source to sound data. Error: {err:?}")
                     });
@@ -261,10 +376,12 @@ impl Default for DspManager {
 /// This is only available when the `kira` feature is enabled.
 ///
 /// The `DspAssets` resource is initialized before the post-startup stage.
+#[cfg(feature = "kira")]
 pub struct DspAssets {
     handles: HashMap<TypeId, Handle<AudioSource>>,
 }
 
+#[cfg(feature = "kira")]
 impl DspAssets {
     fn new(handles: HashMap<TypeId, Handle<AudioSource>>) -> Self {
         Self { handles }
@@ -304,6 +421,53 @@ impl DspAssets {
     }
 }
 
+/// Hashmap for handles of native `bevy_audio` audio sources for DSP graphs.
+///
+/// This is only available when the `bevy_audio` feature is enabled.
+///
+/// The `DspNativeAssets` resource is initialized before the post-startup stage.
+#[cfg(feature = "bevy_audio")]
+pub struct DspNativeAssets {
+    handles: HashMap<TypeId, Handle<DspAudioSource>>,
+}
+
+#[cfg(feature = "bevy_audio")]
+impl DspNativeAssets {
+    fn new(handles: HashMap<TypeId, Handle<DspAudioSource>>) -> Self {
+        Self { handles }
+    }
+
+    /// Get a handle to the audio source from the assets.
+    pub fn get_graph<X, F>(&self, f: F) -> Option<&Handle<DspAudioSource>>
+    where
+        X: AudioUnit32 + 'static,
+        F: Fn() -> X + 'static,
+    {
+        self.handles.get(&Any::type_id(&f))
+    }
+
+    /// Get a handle to the audio source from the assets.
+    ///
+    /// # Panics
+    ///
+    /// This panics when the given function is not found in the assets map.
+    pub fn graph<X, F>(&self, f: F) -> Handle<DspAudioSource>
+    where
+        X: AudioUnit32 + 'static,
+        F: Fn() -> X + 'static,
+    {
+        self.handles
+            .get(&Any::type_id(&f))
+            .unwrap_or_else(|| {
+                panic!(
+                    "DSP asset does not exist with the key {:?}.",
+                    type_name::<F>()
+                )
+            })
+            .clone()
+    }
+}
+
 /// A Bevy plugin for adding DSP graphs.
 ///
 /// Add this plugin to your Bevy app
@@ -332,11 +496,49 @@ impl Plugin for DspPlugin {
             SystemStage::parallel(),
         );
 
+        #[cfg(feature = "kira")]
         app.add_startup_system_to_stage(AddDspAssetsStage, generate_assets);
+        app.add_startup_system_to_stage(AddDspAssetsStage, generate_parameters);
+
+        #[cfg(feature = "bevy_audio")]
+        {
+            use bevy::audio::AddAudioSource;
+            app.add_audio_source::<DspAudioSource>();
+            app.add_startup_system_to_stage(AddDspAssetsStage, generate_native_assets);
+        }
+
+        app.add_system(params::advance_tweens);
     }
 }
 
+/// System to generate assets from [`DspManager`] for Bevy's native `bevy_audio` backend.
+#[cfg(feature = "bevy_audio")]
+#[allow(clippy::needless_pass_by_value)]
+fn generate_native_assets(
+    mut commands: Commands,
+    dsp_manager: Res<DspManager>,
+    mut assets: ResMut<Assets<DspAudioSource>>,
+) {
+    let assets = dsp_manager.add_native_assets(&mut assets);
+    commands.insert_resource(assets);
+}
+
+/// System to generate the [`DspParameters`] resource from every graph's registered parameters.
+#[allow(clippy::needless_pass_by_value)]
+fn generate_parameters(mut commands: Commands, dsp_manager: Res<DspManager>) {
+    let mut parameters = DspParameters::default();
+
+    for graph in dsp_manager.graphs.values() {
+        for handle in graph.params.values() {
+            parameters.register(handle.clone());
+        }
+    }
+
+    commands.insert_resource(parameters);
+}
+
 /// System to generate assets from [`DspManager`]
+#[cfg(feature = "kira")]
 #[allow(clippy::needless_pass_by_value)]
 fn generate_assets(
     mut commands: Commands,
@@ -0,0 +1,130 @@
+//! Native `bevy_audio` backend, usable without `kira`.
+//!
+//! Enabling the `bevy_audio` feature lets a DSP graph be played through stock Bevy audio: a
+//! [`DspAudioSource`] asset implements [`Decodable`], ticking the graph one sample at a time from
+//! a [`Source`]-compatible [`DspAudioDecoder`] instead of pre-rendering into a `kira` sound.
+
+use std::{sync::Arc, time::Duration};
+
+use bevy::reflect::TypeUuid;
+use fundsp::hacker32::AudioUnit32;
+use rodio::Source;
+
+use crate::FnDspGraph;
+
+/// An audio asset, generated from a DSP graph, that can be played through Bevy's native
+/// `bevy_audio` backend.
+///
+/// Produced by [`DspManager::add_native_assets`](crate::DspManager::add_native_assets). The
+/// underlying graph is regenerated for every [`Decodable::decoder`] call, so the same source can
+/// be played more than once concurrently, each with its own, independent ticking state.
+#[derive(TypeUuid)]
+#[uuid = "a7f7e4d0-9f87-4f93-9f0e-9b8e9f8f0d9e"]
+pub struct DspAudioSource {
+    func: Arc<dyn FnDspGraph>,
+    length: f64,
+    sample_rate: u32,
+}
+
+impl DspAudioSource {
+    pub(crate) fn new(func: Arc<dyn FnDspGraph>, length: f64, sample_rate: u32) -> Self {
+        Self {
+            func,
+            length,
+            sample_rate,
+        }
+    }
+}
+
+impl bevy::audio::Decodable for DspAudioSource {
+    type Decoder = DspAudioDecoder;
+    type DecoderItem = f32;
+
+    fn decoder(&self) -> Self::Decoder {
+        DspAudioDecoder::new(self.func.generate_graph(), self.length, self.sample_rate)
+    }
+}
+
+/// A [`rodio::Source`]/[`Iterator`] that ticks a boxed [`AudioUnit32`] one sample at a time to
+/// serve as a [`DspAudioSource`]'s decoder.
+pub struct DspAudioDecoder {
+    graph: Box<dyn AudioUnit32>,
+    channels: u16,
+    sample_rate: u32,
+    total_frames: Option<u64>,
+    frames_emitted: u64,
+    next_channel: u16,
+    input_scratch: Vec<f32>,
+    output_scratch: Vec<f32>,
+}
+
+impl DspAudioDecoder {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn new(graph: Box<dyn AudioUnit32>, length: f64, sample_rate: u32) -> Self {
+        let input_scratch = vec![0.0; graph.inputs()];
+        let output_scratch = vec![0.0; graph.outputs().max(1)];
+        let channels = output_scratch.len() as u16;
+
+        let total_frames = length
+            .is_finite()
+            .then(|| (length * f64::from(sample_rate)).round() as u64);
+
+        Self {
+            graph,
+            channels,
+            sample_rate,
+            total_frames,
+            frames_emitted: 0,
+            next_channel: 0,
+            input_scratch,
+            output_scratch,
+        }
+    }
+}
+
+impl Iterator for DspAudioDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if let Some(total_frames) = self.total_frames {
+            if self.frames_emitted >= total_frames {
+                return None;
+            }
+        }
+
+        if self.next_channel == 0 {
+            self.graph
+                .tick(&self.input_scratch, &mut self.output_scratch);
+        }
+
+        let sample = self.output_scratch[self.next_channel as usize];
+
+        self.next_channel += 1;
+        if self.next_channel >= self.channels {
+            self.next_channel = 0;
+            self.frames_emitted += 1;
+        }
+
+        Some(sample)
+    }
+}
+
+impl Source for DspAudioDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn total_duration(&self) -> Option<Duration> {
+        self.total_frames
+            .map(|frames| Duration::from_secs_f64(frames as f64 / f64::from(self.sample_rate)))
+    }
+}
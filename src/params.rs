@@ -0,0 +1,127 @@
+//! Runtime-tweakable parameters for DSP graphs.
+//!
+//! A [`DspGraph`](crate::DspGraph) built from a plain [`FnDspGraph`](crate::FnDspGraph) is frozen:
+//! every time it is regenerated you get a brand new graph with no link back to the previous one.
+//! Registering named parameters with [`DspManager::add_graph_with_params`](crate::DspManager::add_graph_with_params)
+//! instead captures a [`Shared`] atomic (via `fundsp`'s `shared`/`var` nodes) in the graph closure
+//! and returns a [`ParamHandle`] to it, so writing to that handle from a Bevy system changes the
+//! sound of an already-playing graph.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::{Res, Time};
+use fundsp::hacker32::Shared;
+
+/// A single in-flight linear (or smoothstepped) transition of a parameter towards a target value.
+struct Tween {
+    start: f32,
+    target: f32,
+    duration: f32,
+    elapsed: f32,
+    smoothstep: bool,
+}
+
+impl Tween {
+    fn value_at(&self, elapsed: f32) -> f32 {
+        let t = (elapsed / self.duration).min(1.0);
+        let t = if self.smoothstep {
+            t * t * (3.0 - 2.0 * t)
+        } else {
+            t
+        };
+        self.start + (self.target - self.start) * t
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// A handle to a single named, runtime-tweakable parameter of a DSP graph.
+///
+/// Returned by [`DspManager::add_graph_with_params`](crate::DspManager::add_graph_with_params).
+/// The graph reads this through a `var(&shared)` node, so calling [`ParamHandle::set`] or
+/// [`ParamHandle::tween`] changes the running sound without rebuilding the graph. Cloning a
+/// handle shares the same underlying parameter and any in-flight tween, so it is cheap to stash
+/// a clone in your own resource.
+#[derive(Clone)]
+pub struct ParamHandle {
+    shared: Shared,
+    tween: Arc<Mutex<Option<Tween>>>,
+}
+
+impl ParamHandle {
+    pub(crate) fn new(shared: Shared) -> Self {
+        Self {
+            shared,
+            tween: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The parameter's current value.
+    #[must_use]
+    pub fn value(&self) -> f32 {
+        self.shared.value()
+    }
+
+    /// Immediately sets the parameter's value, cancelling any in-flight tween.
+    pub fn set(&self, value: f32) {
+        *self.tween.lock().unwrap() = None;
+        self.shared.set_value(value);
+    }
+
+    /// Smoothly transitions the parameter to `target` over `duration` seconds, advanced once per
+    /// frame by [`DspPlugin`](crate::DspPlugin).
+    ///
+    /// `smoothstep` selects an ease-in/ease-out curve (`t * t * (3 - 2t)`) instead of a linear
+    /// ramp.
+    pub fn tween(&self, target: f32, duration: f32, smoothstep: bool) {
+        *self.tween.lock().unwrap() = Some(Tween {
+            start: self.value(),
+            target,
+            duration,
+            elapsed: 0.0,
+            smoothstep,
+        });
+    }
+
+    fn advance(&self, dt: f32) {
+        let mut tween = self.tween.lock().unwrap();
+        let Some(active) = tween.as_mut() else {
+            return;
+        };
+
+        active.elapsed += dt;
+        self.shared.set_value(active.value_at(active.elapsed));
+
+        if active.is_finished() {
+            *tween = None;
+        }
+    }
+}
+
+/// Keeps every [`ParamHandle`] registered through
+/// [`DspManager::add_graph_with_params`](crate::DspManager::add_graph_with_params) alive so
+/// [`DspPlugin`](crate::DspPlugin) can advance their tweens once per frame.
+///
+/// This is automatically added as a resource by [`DspPlugin`](crate::DspPlugin).
+#[derive(Default)]
+pub struct DspParameters {
+    handles: Vec<ParamHandle>,
+}
+
+impl DspParameters {
+    pub(crate) fn register(&mut self, handle: ParamHandle) {
+        self.handles.push(handle);
+    }
+}
+
+/// System that advances every in-flight tween by one frame and writes the result into its
+/// `Shared` atomic.
+pub(crate) fn advance_tweens(params: Res<DspParameters>, time: Res<Time>) {
+    let dt = time.delta_seconds();
+
+    for handle in &params.handles {
+        handle.advance(dt);
+    }
+}
@@ -0,0 +1,97 @@
+//! Real-time, pull-based playback of a DSP graph.
+//!
+//! Unlike [`DspSource::generate_raw_bytes`](crate::DspSource::generate_raw_bytes), which renders
+//! the whole graph up front into a fixed-length buffer, [`StreamingDspSource`] ticks the graph one
+//! frame at a time from inside the audio callback. This allows graphs with `length ==
+//! f64::INFINITY` (endless drones, procedural ambience, ...) that would otherwise never fit in
+//! memory.
+
+use fundsp::hacker32::AudioUnit32;
+use kira::dsp::Frame;
+use kira::sound::{Sound, SoundData};
+
+use crate::DspSource;
+
+impl DspSource {
+    /// Turns this source into a [`StreamingDspSource`] that ticks the graph one frame at a time
+    /// instead of rendering it up front.
+    ///
+    /// With `kira`, this is the only way to play a graph whose `length` is `f64::INFINITY`.
+    #[must_use]
+    pub fn into_stream(self, sample_rate: f64) -> StreamingDspSource {
+        StreamingDspSource::new(self.graph, self.length, sample_rate)
+    }
+}
+
+/// A [`SoundData`]/[`Sound`] pair that ticks a boxed [`AudioUnit32`] one frame at a time.
+///
+/// This mirrors the pull-based `AudioStream`/`Frame` model that `bevy_kira_audio` used for its
+/// older streaming API: on every callback the audio thread asks for the next [`Frame`], and this
+/// struct answers by ticking the graph rather than reading from a pre-rendered buffer.
+///
+/// `AudioUnit32` is `Send` but not `Sync`, so a [`StreamingDspSource`] cannot be shared between
+/// threads; only the audio thread that owns it after [`SoundData::into_sound`] may tick it.
+pub struct StreamingDspSource {
+    graph: Box<dyn AudioUnit32>,
+    length: f64,
+    sample_rate: f64,
+    elapsed_frames: u64,
+    /// Reused on every tick so ticking never allocates.
+    input_scratch: Vec<f32>,
+    output_scratch: Vec<f32>,
+}
+
+impl StreamingDspSource {
+    fn new(graph: Box<dyn AudioUnit32>, length: f64, sample_rate: f64) -> Self {
+        let input_scratch = vec![0.0; graph.inputs()];
+        let output_scratch = vec![0.0; graph.outputs()];
+
+        Self {
+            graph,
+            length,
+            sample_rate,
+            elapsed_frames: 0,
+            input_scratch,
+            output_scratch,
+        }
+    }
+
+    /// Whether the source has reached its `length`.
+    ///
+    /// A source created with `length == f64::INFINITY` never finishes.
+    #[must_use]
+    fn has_finished(&self) -> bool {
+        self.length.is_finite() && self.elapsed_frames as f64 / self.sample_rate >= self.length
+    }
+}
+
+impl SoundData for StreamingDspSource {
+    type Error = std::convert::Infallible;
+    type Handle = ();
+
+    fn into_sound(self: Box<Self>) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+        Ok((self, ()))
+    }
+}
+
+impl Sound for StreamingDspSource {
+    fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    fn process(&mut self) -> Frame {
+        self.graph
+            .tick(&self.input_scratch, &mut self.output_scratch);
+        self.elapsed_frames += 1;
+
+        match self.output_scratch.as_slice() {
+            [mono] => Frame::from_mono(*mono),
+            [left, right, ..] => Frame::new(*left, *right),
+            [] => Frame::ZERO,
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.has_finished()
+    }
+}